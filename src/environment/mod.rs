@@ -5,11 +5,9 @@ use std::{cell::RefCell, fmt::Display, panic, rc::Rc};
 
 use environment::{Environment, MutEnv};
 
-use crate::{expression::Expr, interpreter::{self, Interpreter}, returner::Return, statement::Stmt, token::Token};
+use crate::{expression::Expr, interpreter::{Interpreter, RuntimeError, Unwind}, statement::Stmt, token::Token};
 
-pub type BObject = Box<Object>;
-pub type BuiltinSignature = fn(Box<[BObject]>) -> BObject;
-pub type Args = Box<[BObject]>;
+pub type BuiltinSignature = fn(Vec<Object>) -> Result<Object, RuntimeError>;
 
 
 #[derive(Debug, Clone, PartialEq)]
@@ -19,14 +17,18 @@ pub enum Object {
     String(String),
     Nil,
     Unitialized,
-    Return(BObject),
     Function{
         name: Box<Token>,
         params: Box<[Token]>,
         body: Box<[Box<Stmt>]>,
         environment: MutEnv
     },
-    Builtin(String, BuiltinSignature),
+    Builtin(String, BuiltinSignature, usize),
+    Array(Rc<RefCell<Vec<Object>>>),
+    /// Entries rather than a `HashMap`: `Object` has no `Hash`/`Eq` impl, so
+    /// lookups walk the entries and compare keys with `is_equal`, same as
+    /// everywhere else values are compared in this interpreter.
+    Map(Rc<RefCell<Vec<(Object, Object)>>>),
 }
 
 pub fn csv_str<T: Display>(arr: &[T]) -> String {
@@ -54,9 +56,62 @@ impl Object {
             (Object::Number(a1), Object::Number(a2)) => a1.clone() == a2,
             (Object::Boolean(a1), Object::Boolean(a2)) => a1.clone() == a2,
             (Object::String(a1), Object::String(a2)) => *a1 == a2,
+            (Object::Array(a1), Object::Array(a2)) => Rc::ptr_eq(a1, &a2),
+            (Object::Map(a1), Object::Map(a2)) => Rc::ptr_eq(a1, &a2),
             _ => false
         }
     }
+
+    /// `target[index]`, used by `Expr::Index`.
+    pub fn index_get(&self, index: &Object, bracket: &Token) -> Result<Object, RuntimeError> {
+        match self {
+            Object::Array(items) => {
+                let items = items.borrow();
+                let idx = Object::array_index(index, items.len(), bracket)?;
+                Ok(items[idx].to_owned())
+            },
+            Object::Map(entries) => {
+                entries.borrow().iter()
+                    .find(|(key, _)| key.is_equal(index.to_owned()))
+                    .map(|(_, value)| value.to_owned())
+                    .ok_or_else(|| RuntimeError{ token: bracket.to_owned(), message: "Key not found.".to_string() })
+            },
+            _ => Err(RuntimeError{ token: bracket.to_owned(), message: "Only arrays and maps can be indexed.".to_string() }),
+        }
+    }
+
+    /// `target[index] = value`, shared by the indexed-assignment expression.
+    pub fn index_set(&self, index: Object, value: Object, bracket: &Token) -> Result<(), RuntimeError> {
+        match self {
+            Object::Array(items) => {
+                let mut items = items.borrow_mut();
+                let idx = Object::array_index(&index, items.len(), bracket)?;
+                items[idx] = value;
+                Ok(())
+            },
+            Object::Map(entries) => {
+                let mut entries = entries.borrow_mut();
+                match entries.iter_mut().find(|(key, _)| key.is_equal(index.to_owned())) {
+                    Some(entry) => entry.1 = value,
+                    None => entries.push((index, value)),
+                }
+                Ok(())
+            },
+            _ => Err(RuntimeError{ token: bracket.to_owned(), message: "Only arrays and maps can be indexed.".to_string() }),
+        }
+    }
+
+    fn array_index(index: &Object, len: usize, bracket: &Token) -> Result<usize, RuntimeError> {
+        let Object::Number(n) = index else {
+            return Err(RuntimeError{ token: bracket.to_owned(), message: "Array index must be a number.".to_string() });
+        };
+
+        if n.fract() != 0.0 || *n < 0.0 || *n as usize >= len {
+            return Err(RuntimeError{ token: bracket.to_owned(), message: "Array index out of range.".to_string() });
+        }
+
+        Ok(*n as usize)
+    }
 }
 
 impl Display for Object {
@@ -67,26 +122,31 @@ impl Display for Object {
             Object::String(s) => write!(f, "{}", s),
             Object::Nil => write!(f, "nil"),
             Object::Unitialized => write!(f, "unitialized"),
-            Object::Return(object) => write!(f, "return {}", object),
             Object::Function{params, body, name, ..} => {
                 write!(f, "fn {}({:?}) {:?}", name.lexeme, csv_str(params), body)
             }
-            Object::Builtin(name, _) => write!(f, "{}", name),
+            Object::Builtin(name, ..) => write!(f, "<native fn {}>", name),
+            Object::Array(items) => write!(f, "[{}]", csv_str(&items.borrow())),
+            Object::Map(entries) => {
+                let entries = entries.borrow();
+                let pairs: Vec<String> = entries.iter().map(|(k, v)| format!("{}: {}", k, v)).collect();
+                write!(f, "{{{}}}", pairs.join(", "))
+            },
         }
     }
 
     
 }
 
-impl ObjectCaller<BObject> for Object{
+impl ObjectCaller<Result<Object, RuntimeError>> for Object{
     fn is_callable(&self) -> bool{
         match self {
             Object::Function{..} => true,
-            Object::Builtin(_, _) => true,
+            Object::Builtin(..) => true,
             _ => false
         }
     }
-    fn call(&mut self, interpreter: &mut Interpreter, arguments: Box<[BObject]>) -> BObject {
+    fn call(&mut self, interpreter: &mut Interpreter, arguments: Vec<Object>) -> Result<Object, RuntimeError> {
         match &self {
             Object::Function{body, name, params, environment} => {
                 let mut env = Environment::new_enclosing(environment.clone());
@@ -97,17 +157,25 @@ impl ObjectCaller<BObject> for Object{
                     i += 1;
                 }
 
-                interpreter.execute_block(body, Rc::new(RefCell::new(env)));
-                Return::get()
+                match interpreter.execute_block(body, Rc::new(RefCell::new(env))) {
+                    Ok(()) => Ok(Object::Nil),
+                    Err(Unwind::Return(value)) => Ok(value),
+                    Err(Unwind::Error(error)) => Err(error),
+                    // The resolver rejects `break`/`continue` outside a loop before this
+                    // ever runs; this is only a defense-in-depth backstop.
+                    Err(Unwind::Break) => Err(RuntimeError{ token: (**name).to_owned(), message: "Can't use 'break' outside of a loop.".to_string() }),
+                    Err(Unwind::Continue) => Err(RuntimeError{ token: (**name).to_owned(), message: "Can't use 'continue' outside of a loop.".to_string() }),
+                }
             },
-            Object::Builtin(_, func) => func(arguments),
-            _ => Box::new(Object::Nil)
+            Object::Builtin(_, func, _) => func(arguments),
+            _ => Ok(Object::Nil)
         }
     }
-    
+
     fn arity(&self) -> usize {
         match self {
             Object::Function{params, ..} => params.len(),
+            Object::Builtin(_, _, arity) => *arity,
             _ => 0
         }
     }
@@ -115,6 +183,6 @@ impl ObjectCaller<BObject> for Object{
 
 pub trait ObjectCaller<R> {
     fn is_callable(&self) -> bool;
-    fn call(&mut self, interpreter: &mut Interpreter, arguments: Box<[BObject]>) -> R;
+    fn call(&mut self, interpreter: &mut Interpreter, arguments: Vec<Object>) -> R;
     fn arity(&self) -> usize;
 }
\ No newline at end of file
@@ -1,82 +1,145 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, fmt::Display, rc::Rc};
 
-use crate::{environment::environment::{Environment, MutEnv}, error::ErrorHandler, expression::{Expr, ExprVisitor}, environment::{Object, ObjectCaller}, statement::{Stmt, StmtVisitor}, token::{Token, TokenType}};
+use crate::{environment::environment::{Environment, MutEnv}, expression::{Expr, ExprVisitor}, environment::{Object, ObjectCaller}, statement::{Stmt, StmtVisitor}, token::{Token, TokenType}};
+
+/// A runtime type/arity/etc. error raised while evaluating an expression or
+/// executing a statement. Carries the offending token so callers can report
+/// the line it happened on.
+#[derive(Debug, Clone)]
+pub struct RuntimeError {
+    pub token: Token,
+    pub message: String,
+}
+
+impl Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{}\n[line {}]", self.message, self.token.line)
+    }
+}
+
+/// Non-local control transfer raised while executing a statement: either a
+/// genuine runtime error, or one of the `return`/`break`/`continue` signals
+/// that unwind the statement stack up to the nearest function/loop boundary.
+#[derive(Debug, Clone)]
+pub enum Unwind {
+    Return(Object),
+    Break,
+    Continue,
+    Error(RuntimeError),
+}
+
+impl From<RuntimeError> for Unwind {
+    fn from(error: RuntimeError) -> Self {
+        Unwind::Error(error)
+    }
+}
 
 pub struct Interpreter{
     environment: MutEnv,
-    pub globals: MutEnv
+    pub globals: MutEnv,
+    /// Scope-hop counts produced by the `Resolver` for each `Expr::Variable`
+    /// and `Expr::Assign`, keyed by that expression's stable id. An id with
+    /// no entry here is a global, looked up by name instead.
+    locals: HashMap<usize, usize>,
 }
 
 impl Interpreter {
     pub fn new() -> Self {
         let environment = Rc::new(RefCell::new(Environment::new()));
 
+        crate::stdlib::load(&environment);
+
         Self{
             environment: environment.to_owned(),
-            globals: environment.clone()
+            globals: environment.clone(),
+            locals: HashMap::new(),
         }
     }
 
-    pub fn evaluate_expr(&mut self, expr: &Box<Expr>) -> Object {
+    pub fn evaluate_expr(&mut self, expr: &Box<Expr>) -> Result<Object, RuntimeError> {
         expr.accept(self)
     }
 
-    pub fn evaluate_stmt(&mut self, stmt: &Box<Stmt>) -> (){
+    pub fn evaluate_stmt(&mut self, stmt: &Box<Stmt>) -> Result<(), Unwind>{
         stmt.accept(self)
     }
 
-    fn runtime_error(operator: &Token, message: String) -> Object{
-        ErrorHandler::runtime_error(operator, String::from(message));
-        Object::Nil
+    /// Recorded by the `Resolver` before interpretation begins.
+    pub fn resolve(&mut self, id: usize, depth: usize) {
+        self.locals.insert(id, depth);
+    }
+
+    fn lookup_variable(&mut self, name: &Token, id: usize) -> Object {
+        match self.locals.get(&id) {
+            Some(distance) => self.environment.borrow_mut().get_at(*distance, name.to_owned()),
+            None => self.globals.borrow_mut().get(name.to_owned()),
+        }
     }
 
-    pub fn execute_block(&mut self, statements: &Vec<Box<Stmt>>, environment: MutEnv){
+    fn runtime_error(operator: &Token, message: String) -> Result<Object, RuntimeError>{
+        Err(RuntimeError{ token: operator.to_owned(), message })
+    }
+
+    pub fn execute_block(&mut self, statements: &Vec<Box<Stmt>>, environment: MutEnv) -> Result<(), Unwind>{
         let previous = self.environment.to_owned();
 
         self.environment = environment;
 
         for stmt in statements{
-            self.evaluate_stmt(stmt)
+            if let Err(unwind) = self.evaluate_stmt(stmt) {
+                self.environment = previous;
+                return Err(unwind);
+            }
         }
 
         self.environment = previous;
+        Ok(())
     }
 }
 
-impl StmtVisitor<()> for Interpreter {
-    fn visit(&mut self, stmt: &Stmt) -> () {
+impl StmtVisitor<Result<(), Unwind>> for Interpreter {
+    fn visit(&mut self, stmt: &Stmt) -> Result<(), Unwind> {
         match stmt {
             Stmt::Print { expression } => {
-                let value = self.evaluate_expr(expression);
-                println!("{}", value)
+                let value = self.evaluate_expr(expression)?;
+                println!("{}", value);
+                Ok(())
             },
             Stmt::Expression { expression } => {
-                self.evaluate_expr(expression);
+                self.evaluate_expr(expression)?;
+                Ok(())
             },
             Stmt::Block { statements } => {
                 let new_enw = Environment::new_enclosing(self.environment.to_owned());
 
-                self.execute_block(statements, 
+                self.execute_block(statements,
                     Rc::new(RefCell::new(new_enw)))
             },
             Stmt::Var { name, initializer } => {
-                let value = self.evaluate_expr(initializer);
-                self.environment.borrow_mut().define(name, value)
+                let value = self.evaluate_expr(initializer)?;
+                self.environment.borrow_mut().define(name, value);
+                Ok(())
             },
             Stmt::While { condition, body } => {
-                while self.evaluate_expr(condition).is_thuthy() {
-                    self.evaluate_stmt(body);
+                while self.evaluate_expr(condition)?.is_thuthy() {
+                    match self.evaluate_stmt(body) {
+                        Ok(()) => (),
+                        Err(Unwind::Break) => break,
+                        Err(Unwind::Continue) => continue,
+                        Err(unwind) => return Err(unwind),
+                    }
                 }
+                Ok(())
             },
             Stmt::If { condition, then_branch, else_branch } => {
-                let condition_result = self.evaluate_expr(condition);
+                let condition_result = self.evaluate_expr(condition)?;
 
                 if condition_result.is_thuthy(){
                     self.evaluate_stmt(then_branch)
                 } else {
                     match else_branch {
                         Some(branch) => self.evaluate_stmt(branch),
-                        None => (),
+                        None => Ok(()),
                     }
                 }
             },
@@ -84,30 +147,46 @@ impl StmtVisitor<()> for Interpreter {
                 let function = Object::Function{
                     body: body.to_owned(),
                     name: Box::new(name.to_owned()),
-                    params: params.to_owned() 
+                    params: params.to_owned(),
+                    environment: self.environment.to_owned(),
                 };
-                self.environment.borrow_mut().define(name, function)
+                self.environment.borrow_mut().define(name, function);
+                Ok(())
             },
+            Stmt::Return { keyword: _, value } => {
+                let value = match value {
+                    Some(expr) => self.evaluate_expr(expr)?,
+                    None => Object::Nil,
+                };
+                Err(Unwind::Return(value))
+            },
+            Stmt::Break { keyword: _ } => Err(Unwind::Break),
+            Stmt::Continue { keyword: _ } => Err(Unwind::Continue),
             _ => panic!("Statement not defined!")
         }
     }
 }
 
-impl ExprVisitor<Object> for Interpreter {
-    fn visit(&mut self, expr: &Expr) -> Object {
+impl ExprVisitor<Result<Object, RuntimeError>> for Interpreter {
+    fn visit(&mut self, expr: &Expr) -> Result<Object, RuntimeError> {
         match expr {
-            Expr::Assign { name, value } => {
-                let value = self.evaluate_expr(value);
-                self.environment.borrow_mut().assign(name, value.to_owned());
-                return value;
+            Expr::Assign { name, value, id } => {
+                let value = self.evaluate_expr(value)?;
+
+                match self.locals.get(id) {
+                    Some(distance) => self.environment.borrow_mut().assign_at(*distance, name, value.to_owned()),
+                    None => self.globals.borrow_mut().assign(name, value.to_owned()),
+                }
+
+                Ok(value)
             },
             Expr::Call { callee, paren, arguments } => {
-                let mut callee = self.evaluate_expr(callee);
+                let mut callee = self.evaluate_expr(callee)?;
 
                 let mut args = vec![];
 
                 for arg in arguments{
-                    args.push(self.evaluate_expr(arg));
+                    args.push(self.evaluate_expr(arg)?);
                 }
 
                 if !callee.is_callable(){
@@ -121,66 +200,156 @@ impl ExprVisitor<Object> for Interpreter {
                 callee.call(self, args)
             },
             Expr::Logical { left, operator, right } => {
-                let left = self.evaluate_expr(left);
+                let left = self.evaluate_expr(left)?;
 
                 if operator.token_type == TokenType::Or{
                     if left.to_owned().is_thuthy() {
-                        return left;
+                        return Ok(left);
                     }
                 } else {
                     if !left.to_owned().is_thuthy(){
-                        return left;
+                        return Ok(left);
                     }
                 }
 
                 self.evaluate_expr(right)
             },
-            Expr::Variable { name } => {
-                self.environment.borrow_mut().get(name.to_owned())
+            Expr::Variable { name, id } => {
+                Ok(self.lookup_variable(name, *id))
+            },
+            Expr::ArrayLiteral { elements } => {
+                let mut values = vec![];
+                for element in elements {
+                    values.push(self.evaluate_expr(element)?);
+                }
+                Ok(Object::Array(Rc::new(RefCell::new(values))))
+            },
+            Expr::MapLiteral { entries } => {
+                let mut values = vec![];
+                for (key, value) in entries {
+                    let key = self.evaluate_expr(key)?;
+                    let value = self.evaluate_expr(value)?;
+                    values.push((key, value));
+                }
+                Ok(Object::Map(Rc::new(RefCell::new(values))))
+            },
+            Expr::Index { target, bracket, index } => {
+                let target = self.evaluate_expr(target)?;
+                let index = self.evaluate_expr(index)?;
+                target.index_get(&index, bracket)
             },
-            Expr::Literal { value } => value.to_owned(),
+            Expr::IndexAssign { target, bracket, index, value } => {
+                let target = self.evaluate_expr(target)?;
+                let index = self.evaluate_expr(index)?;
+                let value = self.evaluate_expr(value)?;
+                target.index_set(index, value.to_owned(), bracket)?;
+                Ok(value)
+            },
+            Expr::Pipeline { left, operator, right } => {
+                let left = self.evaluate_expr(left)?;
+                let mut right = self.evaluate_expr(right)?;
+
+                let Object::Array(items) = left else {
+                    return Interpreter::runtime_error(operator, "Left operand of a pipeline must be an array.".to_string());
+                };
+
+                if !right.is_callable() {
+                    return Interpreter::runtime_error(operator, "Right operand of a pipeline must be callable.".to_string());
+                }
+
+                if right.arity() != 1 {
+                    return Interpreter::runtime_error(operator, format!("Expected {} arguments, but got 1.", right.arity()));
+                }
+
+                match operator.token_type {
+                    TokenType::PipeColon => {
+                        let elements = items.borrow().to_owned();
+                        let mut mapped = vec![];
+                        for item in elements {
+                            mapped.push(right.call(self, vec![item])?);
+                        }
+                        Ok(Object::Array(Rc::new(RefCell::new(mapped))))
+                    },
+                    TokenType::PipeQuestion => {
+                        let elements = items.borrow().to_owned();
+                        let mut filtered = vec![];
+                        for item in elements {
+                            if right.call(self, vec![item.to_owned()])?.is_thuthy() {
+                                filtered.push(item);
+                            }
+                        }
+                        Ok(Object::Array(Rc::new(RefCell::new(filtered))))
+                    },
+                    TokenType::PipeGreater => right.call(self, vec![Object::Array(items)]),
+                    _ => unreachable!(),
+                }
+            },
+            Expr::Literal { value } => Ok(value.to_owned()),
             Expr::Grouping { expression } => self.evaluate_expr(expression),
             Expr::Unary { operator, right } => {
-                let right = self.evaluate_expr(right);
+                let right = self.evaluate_expr(right)?;
 
                 match operator.token_type {
                     TokenType::Bang => {
-                        Object::Boolean(!right.is_thuthy())
+                        Ok(Object::Boolean(!right.is_thuthy()))
                     },
                     TokenType::Minus => match right{
-                        Object::Number(num) => Object::Number(-num),
+                        Object::Number(num) => Ok(Object::Number(-num)),
                         _ => Interpreter::runtime_error(operator, "Operand must be a number.".to_string()),
                     } ,
-                    _ => Object::Nil
+                    _ => Ok(Object::Nil)
                 }
             },
             Expr::Binary { left, operator, right } => {
-                let left = self.evaluate_expr(left);
-                let right = self.evaluate_expr(right);
+                let left = self.evaluate_expr(left)?;
+                let right = self.evaluate_expr(right)?;
 
                 match (left, right) {
                     (Object::String(str1), Object::String(str2)) => {
                         match operator.token_type{
-                            TokenType::Plus => Object::String(str1 + &str2),
+                            TokenType::Plus => Ok(Object::String(str1 + &str2)),
                             TokenType::Slash | TokenType::Star | TokenType::Minus => Interpreter::runtime_error(operator, "Operands must be numbers.".to_string()),
-                            TokenType::BangEqual => Object::Boolean(str1 != str2),
-                            TokenType::EqualEqual => Object::Boolean(str1 == str2),
-                            _ => Object::Nil
+                            TokenType::BangEqual => Ok(Object::Boolean(str1 != str2)),
+                            TokenType::EqualEqual => Ok(Object::Boolean(str1 == str2)),
+                            _ => Ok(Object::Nil)
                         }
                     },
                     (Object::Number(num1), Object::Number(num2)) => {
                         match operator.token_type {
-                            TokenType::Plus => Object::Number(num1 + num2),
-                            TokenType::Minus => Object::Number(num1 - num2),
-                            TokenType::Slash => Object::Number(num1 / num2),
-                            TokenType::Star => Object::Number(num1 * num2),
-                            TokenType::Greater => Object::Boolean(num1 > num2),
-                            TokenType::GreaterEqual => Object::Boolean(num1 >= num2),
-                            TokenType::Less => Object::Boolean(num1 < num2),
-                            TokenType::LessEqual => Object::Boolean(num1 <= num2),
-                            TokenType::BangEqual => Object::Boolean(num1 != num2),
-                            TokenType::EqualEqual => Object::Boolean(num1 == num2),
-                            _ => Object::Number(0.0)
+                            TokenType::Plus => Ok(Object::Number(num1 + num2)),
+                            TokenType::Minus => Ok(Object::Number(num1 - num2)),
+                            TokenType::Slash => Ok(Object::Number(num1 / num2)),
+                            TokenType::Star => Ok(Object::Number(num1 * num2)),
+                            TokenType::Greater => Ok(Object::Boolean(num1 > num2)),
+                            TokenType::GreaterEqual => Ok(Object::Boolean(num1 >= num2)),
+                            TokenType::Less => Ok(Object::Boolean(num1 < num2)),
+                            TokenType::LessEqual => Ok(Object::Boolean(num1 <= num2)),
+                            TokenType::BangEqual => Ok(Object::Boolean(num1 != num2)),
+                            TokenType::EqualEqual => Ok(Object::Boolean(num1 == num2)),
+                            TokenType::Caret => Ok(Object::Number(num1.powf(num2))),
+                            TokenType::Percent => Ok(Object::Number(num1.rem_euclid(num2))),
+                            TokenType::Ampersand | TokenType::Pipe | TokenType::LessLess | TokenType::GreaterGreater => {
+                                if num1.fract() != 0.0 || num2.fract() != 0.0 {
+                                    return Interpreter::runtime_error(operator, "Operands must be whole numbers.".to_string());
+                                }
+
+                                let (lhs, rhs) = (num1 as i64, num2 as i64);
+
+                                if matches!(operator.token_type, TokenType::LessLess | TokenType::GreaterGreater) && !(0..64).contains(&rhs) {
+                                    return Interpreter::runtime_error(operator, "Shift amount must be between 0 and 63.".to_string());
+                                }
+
+                                let result = match operator.token_type {
+                                    TokenType::Ampersand => lhs & rhs,
+                                    TokenType::Pipe => lhs | rhs,
+                                    TokenType::LessLess => lhs << rhs,
+                                    TokenType::GreaterGreater => lhs >> rhs,
+                                    _ => unreachable!(),
+                                };
+
+                                Ok(Object::Number(result as f64))
+                            },
+                            _ => Ok(Object::Number(0.0))
                         }
                     },
                     (val1, val2) => {
@@ -188,14 +357,209 @@ impl ExprVisitor<Object> for Interpreter {
                             TokenType::Greater | TokenType::GreaterEqual | TokenType::Less | TokenType::LessEqual |
                             TokenType::Slash | TokenType::Star | TokenType::Minus => Interpreter::runtime_error(operator, "Operands must be numbers.".to_string()),
                             TokenType::Plus => Interpreter::runtime_error(operator, "Operands must be two numbers or two strings.".to_string()),
-                            TokenType::BangEqual => Object::Boolean(!val1.is_equal(val2)),
-                            TokenType::EqualEqual => Object::Boolean(val1.is_equal(val2)),
-                            _ => Object::Nil
+                            TokenType::BangEqual => Ok(Object::Boolean(!val1.is_equal(val2))),
+                            TokenType::EqualEqual => Ok(Object::Boolean(val1.is_equal(val2))),
+                            _ => Ok(Object::Nil)
                         }
                     }
                 }
             },
-            _ => Object::Nil
+            _ => Ok(Object::Nil)
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tok(token_type: TokenType, lexeme: &str) -> Token {
+        Token { token_type, lexeme: lexeme.to_string(), line: 1 }
+    }
+
+    fn num(n: f64) -> Box<Expr> {
+        Box::new(Expr::Literal { value: Object::Number(n) })
+    }
+
+    fn binary(left: Box<Expr>, operator: Token, right: Box<Expr>) -> Box<Expr> {
+        Box::new(Expr::Binary { left, operator, right })
+    }
+
+    #[test]
+    fn shift_left_rejects_out_of_range_amount() {
+        let mut interpreter = Interpreter::new();
+        let expr = binary(num(1.0), tok(TokenType::LessLess, "<<"), num(100.0));
+        assert!(interpreter.evaluate_expr(&expr).is_err());
+    }
+
+    #[test]
+    fn shift_right_rejects_negative_amount() {
+        let mut interpreter = Interpreter::new();
+        let expr = binary(num(1.0), tok(TokenType::GreaterGreater, ">>"), num(-1.0));
+        assert!(interpreter.evaluate_expr(&expr).is_err());
+    }
+
+    #[test]
+    fn shift_left_applies_within_range() {
+        let mut interpreter = Interpreter::new();
+        let expr = binary(num(1.0), tok(TokenType::LessLess, "<<"), num(4.0));
+        assert_eq!(interpreter.evaluate_expr(&expr).unwrap(), Object::Number(16.0));
+    }
+
+    #[test]
+    fn bitwise_and_rejects_non_integer_operand() {
+        let mut interpreter = Interpreter::new();
+        let expr = binary(num(3.5), tok(TokenType::Ampersand, "&"), num(2.0));
+        let err = interpreter.evaluate_expr(&expr).unwrap_err();
+        assert_eq!(err.message, "Operands must be whole numbers.");
+    }
+
+    #[test]
+    fn array_literal_evaluates_each_element() {
+        let mut interpreter = Interpreter::new();
+        let expr = Box::new(Expr::ArrayLiteral { elements: vec![*num(1.0), *num(2.0), *num(3.0)] });
+
+        let Object::Array(items) = interpreter.evaluate_expr(&expr).unwrap() else {
+            panic!("expected an array");
+        };
+        assert_eq!(*items.borrow(), vec![Object::Number(1.0), Object::Number(2.0), Object::Number(3.0)]);
+    }
+
+    #[test]
+    fn array_literal_evaluated_twice_does_not_alias() {
+        let mut interpreter = Interpreter::new();
+        let expr = Box::new(Expr::ArrayLiteral { elements: vec![*num(1.0)] });
+
+        let Object::Array(first) = interpreter.evaluate_expr(&expr).unwrap() else { panic!("expected an array") };
+        let Object::Array(second) = interpreter.evaluate_expr(&expr).unwrap() else { panic!("expected an array") };
+
+        first.borrow_mut().push(Object::Number(2.0));
+        assert_eq!(second.borrow().len(), 1);
+    }
+
+    #[test]
+    fn array_index_out_of_range_is_a_runtime_error() {
+        let mut interpreter = Interpreter::new();
+        let array = Box::new(Expr::ArrayLiteral { elements: vec![*num(1.0)] });
+        let expr = Box::new(Expr::Index { target: array, bracket: tok(TokenType::LeftBracket, "["), index: num(5.0) });
+        assert!(interpreter.evaluate_expr(&expr).is_err());
+    }
+
+    #[test]
+    fn map_literal_is_looked_up_by_key_equality() {
+        let mut interpreter = Interpreter::new();
+        let key = || Expr::Literal { value: Object::String("a".to_string()) };
+        let map = Box::new(Expr::MapLiteral { entries: vec![(key(), *num(1.0))] });
+        let expr = Box::new(Expr::Index { target: map, bracket: tok(TokenType::LeftBrace, "{"), index: Box::new(key()) });
+        assert_eq!(interpreter.evaluate_expr(&expr).unwrap(), Object::Number(1.0));
+    }
+
+    fn add_one(args: Vec<Object>) -> Result<Object, RuntimeError> {
+        match &args[0] {
+            Object::Number(n) => Ok(Object::Number(n + 1.0)),
+            _ => Ok(Object::Nil),
+        }
+    }
+
+    fn sum_two(_args: Vec<Object>) -> Result<Object, RuntimeError> {
+        Ok(Object::Nil)
+    }
+
+    #[test]
+    fn pipeline_map_applies_unary_callable_to_each_element() {
+        let mut interpreter = Interpreter::new();
+        let array = Box::new(Expr::ArrayLiteral { elements: vec![*num(1.0), *num(2.0)] });
+        let callee = Box::new(Expr::Literal { value: Object::Builtin("add_one".to_string(), add_one, 1) });
+        let expr = Box::new(Expr::Pipeline { left: array, operator: tok(TokenType::PipeColon, "|:"), right: callee });
+
+        let Object::Array(items) = interpreter.evaluate_expr(&expr).unwrap() else { panic!("expected an array") };
+        assert_eq!(*items.borrow(), vec![Object::Number(2.0), Object::Number(3.0)]);
+    }
+
+    #[test]
+    fn pipeline_rejects_callee_with_wrong_arity_instead_of_panicking() {
+        let mut interpreter = Interpreter::new();
+        let array = Box::new(Expr::ArrayLiteral { elements: vec![*num(1.0)] });
+        let callee = Box::new(Expr::Literal { value: Object::Builtin("sum_two".to_string(), sum_two, 2) });
+        let expr = Box::new(Expr::Pipeline { left: array, operator: tok(TokenType::PipeColon, "|:"), right: callee });
+
+        assert!(interpreter.evaluate_expr(&expr).is_err());
+    }
+
+    fn var_decl(name: &Token, initial: f64) -> Box<Stmt> {
+        Box::new(Stmt::Var { name: name.to_owned(), initializer: num(initial) })
+    }
+
+    fn assign(name: &Token, id: usize, value: Box<Expr>) -> Box<Stmt> {
+        Box::new(Stmt::Expression { expression: Box::new(Expr::Assign { name: name.to_owned(), value, id }) })
+    }
+
+    fn read(interpreter: &mut Interpreter, name: &Token, id: usize) -> Object {
+        interpreter.evaluate_expr(&Box::new(Expr::Variable { name: name.to_owned(), id })).unwrap()
+    }
+
+    #[test]
+    fn while_loop_break_stops_iteration_early() {
+        let mut interpreter = Interpreter::new();
+        let counter = tok(TokenType::Identifier, "counter");
+
+        let condition = binary(Box::new(Expr::Variable { name: counter.to_owned(), id: 1 }), tok(TokenType::Less, "<"), num(10.0));
+        let hit_three = binary(Box::new(Expr::Variable { name: counter.to_owned(), id: 1 }), tok(TokenType::EqualEqual, "=="), num(3.0));
+        let increment = assign(&counter, 1, binary(Box::new(Expr::Variable { name: counter.to_owned(), id: 1 }), tok(TokenType::Plus, "+"), num(1.0)));
+
+        let body = Box::new(Stmt::Block { statements: vec![
+            Box::new(Stmt::If { condition: hit_three, then_branch: Box::new(Stmt::Break { keyword: tok(TokenType::Break, "break") }), else_branch: None }),
+            increment,
+        ]});
+
+        interpreter.evaluate_stmt(&var_decl(&counter, 0.0)).unwrap();
+        interpreter.evaluate_stmt(&Box::new(Stmt::While { condition, body })).unwrap();
+
+        assert_eq!(read(&mut interpreter, &counter, 1), Object::Number(3.0));
+    }
+
+    #[test]
+    fn while_loop_continue_skips_rest_of_body() {
+        let mut interpreter = Interpreter::new();
+        let counter = tok(TokenType::Identifier, "counter");
+        let total = tok(TokenType::Identifier, "total");
+
+        let condition = binary(Box::new(Expr::Variable { name: counter.to_owned(), id: 1 }), tok(TokenType::Less, "<"), num(5.0));
+        let increment_counter = assign(&counter, 1, binary(Box::new(Expr::Variable { name: counter.to_owned(), id: 1 }), tok(TokenType::Plus, "+"), num(1.0)));
+        let hit_three = binary(Box::new(Expr::Variable { name: counter.to_owned(), id: 1 }), tok(TokenType::EqualEqual, "=="), num(3.0));
+        let increment_total = assign(&total, 2, binary(Box::new(Expr::Variable { name: total.to_owned(), id: 2 }), tok(TokenType::Plus, "+"), num(1.0)));
+
+        let body = Box::new(Stmt::Block { statements: vec![
+            increment_counter,
+            Box::new(Stmt::If { condition: hit_three, then_branch: Box::new(Stmt::Continue { keyword: tok(TokenType::Continue, "continue") }), else_branch: None }),
+            increment_total,
+        ]});
+
+        interpreter.evaluate_stmt(&var_decl(&counter, 0.0)).unwrap();
+        interpreter.evaluate_stmt(&var_decl(&total, 0.0)).unwrap();
+        interpreter.evaluate_stmt(&Box::new(Stmt::While { condition, body })).unwrap();
+
+        assert_eq!(read(&mut interpreter, &counter, 1), Object::Number(5.0));
+        assert_eq!(read(&mut interpreter, &total, 2), Object::Number(4.0));
+    }
+
+    #[test]
+    fn stray_break_at_a_function_boundary_is_a_runtime_error_not_a_silent_nil() {
+        let mut interpreter = Interpreter::new();
+        let name = tok(TokenType::Identifier, "f");
+
+        interpreter.evaluate_stmt(&Box::new(Stmt::Function {
+            name: name.to_owned(),
+            params: Vec::<Token>::new().into_boxed_slice(),
+            body: vec![Box::new(Stmt::Break { keyword: tok(TokenType::Break, "break") }) as Box<Stmt>].into_boxed_slice(),
+        })).unwrap();
+
+        let call = Box::new(Expr::Call {
+            callee: Box::new(Expr::Variable { name: name.to_owned(), id: 3 }),
+            paren: tok(TokenType::RightParen, ")"),
+            arguments: vec![],
+        });
+
+        assert!(interpreter.evaluate_expr(&call).is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use crate::{
+    expression::{Expr, ExprVisitor},
+    interpreter::Interpreter,
+    statement::{Stmt, StmtVisitor},
+    token::Token,
+};
+
+/// A compile-time error reported by the resolver (a `return` outside a
+/// function, a variable read in its own initializer, ...), distinct from a
+/// `RuntimeError` since it's caught before the interpreter ever runs.
+#[derive(Debug, Clone)]
+pub struct ResolveError {
+    pub token: Token,
+    pub message: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum FunctionType {
+    None,
+    Function,
+}
+
+/// Walks the AST once before interpretation, resolving every variable
+/// reference to the number of enclosing scopes between its use and its
+/// declaration. The interpreter uses that distance to jump straight to the
+/// right environment instead of walking the parent chain by name.
+pub struct Resolver<'a> {
+    interpreter: &'a mut Interpreter,
+    scopes: Vec<HashMap<String, bool>>,
+    current_function: FunctionType,
+    /// Nesting depth of `Stmt::While` bodies, reset at each function boundary
+    /// so `break`/`continue` can't leak out of the function that encloses them.
+    current_loop: usize,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(interpreter: &'a mut Interpreter) -> Self {
+        Self {
+            interpreter,
+            scopes: Vec::new(),
+            current_function: FunctionType::None,
+            current_loop: 0,
+        }
+    }
+
+    pub fn resolve(&mut self, statements: &Vec<Box<Stmt>>) -> Result<(), ResolveError> {
+        for stmt in statements {
+            self.resolve_stmt(stmt)?;
+        }
+        Ok(())
+    }
+
+    fn resolve_stmt(&mut self, stmt: &Box<Stmt>) -> Result<(), ResolveError> {
+        stmt.accept(self)
+    }
+
+    fn resolve_expr(&mut self, expr: &Box<Expr>) -> Result<(), ResolveError> {
+        expr.accept(self)
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, name: &Token) -> Result<(), ResolveError> {
+        if let Some(scope) = self.scopes.last_mut() {
+            if scope.contains_key(&name.lexeme) {
+                return Err(ResolveError {
+                    token: name.to_owned(),
+                    message: "Already a variable with this name in this scope.".to_string(),
+                });
+            }
+            scope.insert(name.lexeme.to_owned(), false);
+        }
+        Ok(())
+    }
+
+    fn define(&mut self, name: &Token) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.lexeme.to_owned(), true);
+        }
+    }
+
+    fn resolve_local(&mut self, name: &Token, id: usize) {
+        for (depth, scope) in self.scopes.iter().rev().enumerate() {
+            if scope.contains_key(&name.lexeme) {
+                self.interpreter.resolve(id, depth);
+                return;
+            }
+        }
+    }
+
+    fn resolve_function(&mut self, params: &[Token], body: &Vec<Box<Stmt>>, kind: FunctionType) -> Result<(), ResolveError> {
+        let enclosing_function = self.current_function;
+        let enclosing_loop = self.current_loop;
+        self.current_function = kind;
+        self.current_loop = 0;
+
+        self.begin_scope();
+        for param in params {
+            self.declare(param)?;
+            self.define(param);
+        }
+        self.resolve(body)?;
+        self.end_scope();
+
+        self.current_function = enclosing_function;
+        self.current_loop = enclosing_loop;
+        Ok(())
+    }
+}
+
+impl<'a> StmtVisitor<Result<(), ResolveError>> for Resolver<'a> {
+    fn visit(&mut self, stmt: &Stmt) -> Result<(), ResolveError> {
+        match stmt {
+            Stmt::Block { statements } => {
+                self.begin_scope();
+                self.resolve(statements)?;
+                self.end_scope();
+                Ok(())
+            },
+            Stmt::Var { name, initializer } => {
+                self.declare(name)?;
+                self.resolve_expr(initializer)?;
+                self.define(name);
+                Ok(())
+            },
+            Stmt::Function { name, params, body } => {
+                self.declare(name)?;
+                self.define(name);
+                self.resolve_function(params, body, FunctionType::Function)
+            },
+            Stmt::Expression { expression } => self.resolve_expr(expression),
+            Stmt::Print { expression } => self.resolve_expr(expression),
+            Stmt::If { condition, then_branch, else_branch } => {
+                self.resolve_expr(condition)?;
+                self.resolve_stmt(then_branch)?;
+                if let Some(branch) = else_branch {
+                    self.resolve_stmt(branch)?;
+                }
+                Ok(())
+            },
+            Stmt::While { condition, body } => {
+                self.resolve_expr(condition)?;
+                self.current_loop += 1;
+                let result = self.resolve_stmt(body);
+                self.current_loop -= 1;
+                result
+            },
+            Stmt::Return { keyword, value } => {
+                if self.current_function == FunctionType::None {
+                    return Err(ResolveError {
+                        token: keyword.to_owned(),
+                        message: "Can't return from top-level code.".to_string(),
+                    });
+                }
+                if let Some(expr) = value {
+                    self.resolve_expr(expr)?;
+                }
+                Ok(())
+            },
+            Stmt::Break { keyword } => {
+                if self.current_loop == 0 {
+                    return Err(ResolveError {
+                        token: keyword.to_owned(),
+                        message: "Can't use 'break' outside of a loop.".to_string(),
+                    });
+                }
+                Ok(())
+            },
+            Stmt::Continue { keyword } => {
+                if self.current_loop == 0 {
+                    return Err(ResolveError {
+                        token: keyword.to_owned(),
+                        message: "Can't use 'continue' outside of a loop.".to_string(),
+                    });
+                }
+                Ok(())
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'a> ExprVisitor<Result<(), ResolveError>> for Resolver<'a> {
+    fn visit(&mut self, expr: &Expr) -> Result<(), ResolveError> {
+        match expr {
+            Expr::Variable { name, id } => {
+                if let Some(scope) = self.scopes.last() {
+                    if scope.get(&name.lexeme) == Some(&false) {
+                        return Err(ResolveError {
+                            token: name.to_owned(),
+                            message: "Can't read local variable in its own initializer.".to_string(),
+                        });
+                    }
+                }
+                self.resolve_local(name, *id);
+                Ok(())
+            },
+            Expr::Assign { name, value, id } => {
+                self.resolve_expr(value)?;
+                self.resolve_local(name, *id);
+                Ok(())
+            },
+            Expr::Binary { left, right, .. } | Expr::Logical { left, right, .. } => {
+                self.resolve_expr(left)?;
+                self.resolve_expr(right)
+            },
+            Expr::Call { callee, arguments, .. } => {
+                self.resolve_expr(callee)?;
+                for argument in arguments {
+                    self.resolve_expr(argument)?;
+                }
+                Ok(())
+            },
+            Expr::ArrayLiteral { elements } => {
+                for element in elements {
+                    self.resolve_expr(element)?;
+                }
+                Ok(())
+            },
+            Expr::MapLiteral { entries } => {
+                for (key, value) in entries {
+                    self.resolve_expr(key)?;
+                    self.resolve_expr(value)?;
+                }
+                Ok(())
+            },
+            Expr::Index { target, index, .. } => {
+                self.resolve_expr(target)?;
+                self.resolve_expr(index)
+            },
+            Expr::IndexAssign { target, index, value, .. } => {
+                self.resolve_expr(target)?;
+                self.resolve_expr(index)?;
+                self.resolve_expr(value)
+            },
+            Expr::Pipeline { left, right, .. } => {
+                self.resolve_expr(left)?;
+                self.resolve_expr(right)
+            },
+            Expr::Grouping { expression } => self.resolve_expr(expression),
+            Expr::Unary { right, .. } => self.resolve_expr(right),
+            Expr::Literal { .. } => Ok(()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::Object;
+    use crate::token::TokenType;
+
+    fn tok(token_type: TokenType, lexeme: &str) -> Token {
+        Token { token_type, lexeme: lexeme.to_string(), line: 1 }
+    }
+
+    fn num(n: f64) -> Box<Expr> {
+        Box::new(Expr::Literal { value: Object::Number(n) })
+    }
+
+    fn var(name: &Token, id: usize) -> Box<Expr> {
+        Box::new(Expr::Variable { name: name.to_owned(), id })
+    }
+
+    fn call(callee: Box<Expr>) -> Box<Expr> {
+        Box::new(Expr::Call { callee, paren: tok(TokenType::RightParen, ")"), arguments: vec![] })
+    }
+
+    /// `make_counter()` returns a closure over its own local `count`, proving
+    /// (end to end, through the resolver and the interpreter together) that
+    /// a function captures its *definition-site* environment: calling the
+    /// returned counter repeatedly keeps incrementing the count it closed over.
+    #[test]
+    fn closures_capture_the_definition_site_environment() {
+        let make_counter = tok(TokenType::Identifier, "make_counter");
+        let count = tok(TokenType::Identifier, "count");
+        let increment = tok(TokenType::Identifier, "increment");
+        let counter = tok(TokenType::Identifier, "counter");
+        let result1 = tok(TokenType::Identifier, "result1");
+        let result2 = tok(TokenType::Identifier, "result2");
+
+        let increment_body: Vec<Box<Stmt>> = vec![
+            Box::new(Stmt::Expression { expression: Box::new(Expr::Assign {
+                name: count.to_owned(),
+                value: Box::new(Expr::Binary { left: var(&count, 1), operator: tok(TokenType::Plus, "+"), right: num(1.0) }),
+                id: 2,
+            })}),
+            Box::new(Stmt::Return { keyword: tok(TokenType::Return, "return"), value: Some(var(&count, 3)) }),
+        ];
+
+        let make_counter_body: Vec<Box<Stmt>> = vec![
+            Box::new(Stmt::Var { name: count.to_owned(), initializer: num(0.0) }),
+            Box::new(Stmt::Function { name: increment.to_owned(), params: Vec::<Token>::new().into_boxed_slice(), body: increment_body.into_boxed_slice() }),
+            Box::new(Stmt::Return { keyword: tok(TokenType::Return, "return"), value: Some(var(&increment, 4)) }),
+        ];
+
+        let program: Vec<Box<Stmt>> = vec![
+            Box::new(Stmt::Function { name: make_counter.to_owned(), params: Vec::<Token>::new().into_boxed_slice(), body: make_counter_body.into_boxed_slice() }),
+            Box::new(Stmt::Var { name: counter.to_owned(), initializer: call(var(&make_counter, 5)) }),
+            Box::new(Stmt::Var { name: result1.to_owned(), initializer: call(var(&counter, 6)) }),
+            Box::new(Stmt::Var { name: result2.to_owned(), initializer: call(var(&counter, 7)) }),
+        ];
+
+        let mut interpreter = Interpreter::new();
+        Resolver::new(&mut interpreter).resolve(&program).unwrap();
+
+        for stmt in &program {
+            interpreter.evaluate_stmt(stmt).unwrap();
+        }
+
+        assert_eq!(interpreter.evaluate_expr(&var(&result1, 8)).unwrap(), Object::Number(1.0));
+        assert_eq!(interpreter.evaluate_expr(&var(&result2, 9)).unwrap(), Object::Number(2.0));
+    }
+}
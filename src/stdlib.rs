@@ -0,0 +1,81 @@
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::environment::environment::MutEnv;
+use crate::environment::Object;
+use crate::interpreter::RuntimeError;
+use crate::token::{Token, TokenType};
+
+/// Installs the native standard library into `globals`. Called once from
+/// `Interpreter::new` so every program has `clock`, `len`, `str`, `num`,
+/// `input` and `println` available without an explicit import.
+pub fn load(globals: &MutEnv) {
+    define(globals, "clock", 0, clock);
+    define(globals, "len", 1, len);
+    define(globals, "str", 1, str_of);
+    define(globals, "num", 1, num_of);
+    define(globals, "input", 0, input);
+    define(globals, "println", 1, println_of);
+}
+
+fn define(globals: &MutEnv, name: &str, arity: usize, func: crate::environment::BuiltinSignature) {
+    globals.borrow_mut().define_native(name, Object::Builtin(name.to_string(), func, arity));
+}
+
+fn clock(_args: Vec<Object>) -> Result<Object, RuntimeError> {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64();
+
+    Ok(Object::Number(seconds))
+}
+
+fn len(args: Vec<Object>) -> Result<Object, RuntimeError> {
+    let length = match &args[0] {
+        Object::String(s) => s.chars().count(),
+        Object::Array(items) => items.borrow().len(),
+        Object::Map(entries) => entries.borrow().len(),
+        _ => return Err(native_error("len", "Only strings, arrays, and maps have a length.")),
+    };
+
+    Ok(Object::Number(length as f64))
+}
+
+/// Builtins don't receive a call-site token, so errors they raise are
+/// attributed to a synthetic token carrying the native function's own name.
+fn native_error(name: &str, message: &str) -> RuntimeError {
+    RuntimeError {
+        token: Token { token_type: TokenType::Identifier, lexeme: name.to_string(), line: 0 },
+        message: message.to_string(),
+    }
+}
+
+fn str_of(args: Vec<Object>) -> Result<Object, RuntimeError> {
+    Ok(Object::String(args[0].to_string()))
+}
+
+fn num_of(args: Vec<Object>) -> Result<Object, RuntimeError> {
+    let value = match &args[0] {
+        Object::Number(n) => *n,
+        Object::String(s) => s.trim().parse().unwrap_or(f64::NAN),
+        Object::Boolean(b) => if *b { 1.0 } else { 0.0 },
+        _ => f64::NAN,
+    };
+
+    Ok(Object::Number(value))
+}
+
+fn input(_args: Vec<Object>) -> Result<Object, RuntimeError> {
+    io::stdout().flush().ok();
+
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).ok();
+
+    Ok(Object::String(line.trim_end_matches(['\n', '\r']).to_string()))
+}
+
+fn println_of(args: Vec<Object>) -> Result<Object, RuntimeError> {
+    println!("{}", args[0]);
+    Ok(Object::Nil)
+}